@@ -0,0 +1,280 @@
+//! "git bisect for JSON structure": binary-searches the commit history for
+//! the first commit where a (possibly nested) field satisfies a presence/type
+//! predicate, extracting only the handful of versions the bisection visits
+//! instead of the entire history.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::{Parser, ValueEnum};
+use tracing::info;
+
+use esa_act_history::schema;
+
+use crate::{fetch_file_via_jj, get_commit_history, git2_backend::Git2Backend, Backend};
+use crate::{DEFAULT_FILE_TO_EXTRACT, JJ_COMMAND_NAME};
+
+/// Which condition on the field we're bisecting for.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum PredicateKind {
+    /// The field is present in the document.
+    Present,
+    /// The field is absent from the document. Monotonic true -> false rather
+    /// than false -> true, the reverse of `Present`/`Type`, so it can't be
+    /// bisected by the default search; requires `--no-monotonic`.
+    Absent,
+    /// The field's dominant type equals `--type-name`.
+    Type,
+}
+
+/// Bisect the commit history to find where a JSON field first started
+/// matching a predicate (present, absent, or a specific type).
+#[derive(Parser, Debug)]
+pub(crate) struct FindChangeArgs {
+    /// The name of the file to inspect, relative to the repository root.
+    #[arg(short, long, default_value = DEFAULT_FILE_TO_EXTRACT)]
+    file_to_extract: String,
+
+    /// Field path to test on each version of the file. Nested fields use
+    /// dotted paths (e.g. "geometry.coordinates"), and array elements use a
+    /// `[]` suffix (e.g. "features[].id").
+    #[arg(long)]
+    field: String,
+
+    /// The predicate to test the field against.
+    #[arg(long, value_enum)]
+    predicate: PredicateKind,
+
+    /// Required when `--predicate type`: the type name to match, e.g. "Object".
+    #[arg(long, required_if_eq("predicate", "type"))]
+    type_name: Option<String>,
+
+    /// Fall back to a linear scan instead of assuming the predicate is
+    /// monotonic (false, then true) across history.
+    #[arg(long)]
+    no_monotonic: bool,
+
+    /// Override the path to the 'jj' executable if it's not in your system's PATH.
+    #[arg(long)]
+    jj_path: Option<PathBuf>,
+
+    /// Which extraction backend to use.
+    #[arg(long, value_enum, default_value_t = Backend::Jj)]
+    backend: Backend,
+
+    /// Path to the repository root. Only used by the `git2` backend, which
+    /// (unlike the `jj` backend) doesn't assume the current directory.
+    #[arg(long, default_value = ".")]
+    repo_path: PathBuf,
+}
+
+/// Runs the `find-change` subcommand.
+pub(crate) async fn run(args: &FindChangeArgs) -> Result<()> {
+    if args.predicate == PredicateKind::Absent && !args.no_monotonic {
+        anyhow::bail!(
+            "--predicate absent is true, then false across history (the reverse of \
+             present/type), so the default bisection - which assumes false, then true - \
+             would silently walk into the wrong half. Pass --no-monotonic to scan linearly."
+        );
+    }
+    match args.backend {
+        Backend::Jj => run_jj(args).await,
+        Backend::Git2 => run_git2(args).await,
+    }
+}
+
+async fn run_jj(args: &FindChangeArgs) -> Result<()> {
+    let jj_executable = args.jj_path.as_deref().unwrap_or(Path::new(JJ_COMMAND_NAME));
+
+    let mut commits = get_commit_history(jj_executable).await?;
+    sort_oldest_first(&mut commits);
+    info!("Bisecting {} commits for '{}'...", commits.len(), args.field);
+
+    let found = if args.no_monotonic {
+        let mut found = None;
+        for (commit_id, timestamp) in &commits {
+            let content = fetch_file_via_jj_or_empty(jj_executable, commit_id, &args.file_to_extract).await;
+            if matches_predicate_raw(args.predicate, args.type_name.as_deref(), &args.field, &content) {
+                found = Some((commit_id.clone(), timestamp.clone()));
+                break;
+            }
+        }
+        found
+    } else {
+        let mut lo = 0usize;
+        let mut hi = commits.len();
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            let (commit_id, _) = &commits[mid];
+            let content = fetch_file_via_jj_or_empty(jj_executable, commit_id, &args.file_to_extract).await;
+            if matches_predicate_raw(args.predicate, args.type_name.as_deref(), &args.field, &content) {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
+        }
+        commits.get(lo).cloned()
+    };
+
+    report(found);
+    Ok(())
+}
+
+async fn run_git2(args: &FindChangeArgs) -> Result<()> {
+    let repo_path = args.repo_path.clone();
+    let file_to_extract = args.file_to_extract.clone();
+    let field = args.field.clone();
+    let predicate = args.predicate;
+    let type_name = args.type_name.clone();
+    let no_monotonic = args.no_monotonic;
+
+    let found = tokio::task::spawn_blocking(move || -> Result<Option<(String, String)>> {
+        let backend = Git2Backend::open(&repo_path)?;
+        let mut commits = backend.commit_history()?;
+        sort_oldest_first(&mut commits);
+        info!("Bisecting {} commits for '{}'...", commits.len(), field);
+
+        let test = |commit_id: &str| -> Result<bool> {
+            let content = backend
+                .extract_file(commit_id, &file_to_extract)?
+                .unwrap_or_default();
+            Ok(matches_predicate_raw(predicate, type_name.as_deref(), &field, &content))
+        };
+
+        if no_monotonic {
+            for (commit_id, timestamp) in &commits {
+                if test(commit_id)? {
+                    return Ok(Some((commit_id.clone(), timestamp.clone())));
+                }
+            }
+            Ok(None)
+        } else {
+            let mut lo = 0usize;
+            let mut hi = commits.len();
+            while lo < hi {
+                let mid = lo + (hi - lo) / 2;
+                if test(&commits[mid].0)? {
+                    hi = mid;
+                } else {
+                    lo = mid + 1;
+                }
+            }
+            Ok(commits.get(lo).cloned())
+        }
+    })
+    .await
+    .context("git2 backend task panicked")??;
+
+    report(found);
+    Ok(())
+}
+
+/// Fetches `file_to_extract` at `commit_id` via `jj file show`, treating a
+/// missing file (the commit predates the file's existence) as an empty
+/// document rather than an error, since "file absent" is itself a valid
+/// predicate input. Mirrors the git2 path's `unwrap_or_default()`.
+async fn fetch_file_via_jj_or_empty(jj_executable: &Path, commit_id: &str, file_to_extract: &str) -> Vec<u8> {
+    fetch_file_via_jj(jj_executable, commit_id, file_to_extract)
+        .await
+        .unwrap_or_default()
+}
+
+fn matches_predicate_raw(
+    predicate: PredicateKind,
+    type_name: Option<&str>,
+    field: &str,
+    content: &[u8],
+) -> bool {
+    let snapshot = match schema::analyze_bytes(content) {
+        Ok(snapshot) => snapshot,
+        Err(_) => return false,
+    };
+    match predicate {
+        PredicateKind::Present => snapshot.fields.contains_key(field),
+        PredicateKind::Absent => !snapshot.fields.contains_key(field),
+        PredicateKind::Type => snapshot
+            .fields
+            .get(field)
+            .and_then(|stats| schema::dominant_type(&stats.type_counts))
+            .is_some_and(|dominant| Some(dominant) == type_name),
+    }
+}
+
+/// Commit order returned by the backends isn't guaranteed; bisection needs
+/// oldest-to-newest order since field presence/type is assumed monotonic.
+/// Also used by `schema_timeline`, which needs the same ordering to diff
+/// each schema snapshot against its immediate predecessor.
+pub(crate) fn sort_oldest_first(commits: &mut [(String, String)]) {
+    commits.sort_by_key(|(_, timestamp)| timestamp.parse::<i64>().unwrap_or(i64::MAX));
+}
+
+fn report(found: Option<(String, String)>) {
+    match found {
+        Some((commit_id, timestamp)) => {
+            println!("First matching commit: {} @ {}", commit_id, timestamp)
+        }
+        None => println!("No commit in the history satisfies the predicate."),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn present_doc() -> Vec<u8> {
+        br#"[{"a": 1}]"#.to_vec()
+    }
+
+    fn absent_doc() -> Vec<u8> {
+        br#"[{"b": 1}]"#.to_vec()
+    }
+
+    #[test]
+    fn present_predicate_matches_only_when_the_field_exists() {
+        assert!(matches_predicate_raw(PredicateKind::Present, None, "a", &present_doc()));
+        assert!(!matches_predicate_raw(PredicateKind::Present, None, "a", &absent_doc()));
+    }
+
+    #[test]
+    fn absent_predicate_is_the_inverse_of_present() {
+        // This is exactly why absent can't be bisected by the default
+        // false->true search: it goes true->false across history instead.
+        assert!(!matches_predicate_raw(PredicateKind::Absent, None, "a", &present_doc()));
+        assert!(matches_predicate_raw(PredicateKind::Absent, None, "a", &absent_doc()));
+    }
+
+    #[test]
+    fn type_predicate_matches_the_dominant_type() {
+        assert!(matches_predicate_raw(PredicateKind::Type, Some("Number"), "a", &present_doc()));
+        assert!(!matches_predicate_raw(PredicateKind::Type, Some("String"), "a", &present_doc()));
+    }
+
+    #[test]
+    fn sort_oldest_first_orders_by_numeric_timestamp() {
+        let mut commits = vec![
+            ("c2".to_string(), "200".to_string()),
+            ("c1".to_string(), "100".to_string()),
+            ("c3".to_string(), "300".to_string()),
+        ];
+        sort_oldest_first(&mut commits);
+        assert_eq!(
+            commits.iter().map(|(id, _)| id.as_str()).collect::<Vec<_>>(),
+            vec!["c1", "c2", "c3"]
+        );
+    }
+
+    #[tokio::test]
+    async fn run_rejects_absent_predicate_without_no_monotonic() {
+        let args = FindChangeArgs {
+            file_to_extract: DEFAULT_FILE_TO_EXTRACT.to_string(),
+            field: "a".to_string(),
+            predicate: PredicateKind::Absent,
+            type_name: None,
+            no_monotonic: false,
+            jj_path: None,
+            backend: Backend::Jj,
+            repo_path: PathBuf::from("."),
+        };
+        assert!(run(&args).await.is_err());
+    }
+}
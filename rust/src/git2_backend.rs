@@ -0,0 +1,76 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use git2::{Oid, Repository, Sort};
+use tracing::warn;
+
+/// In-process extraction backend built on `git2`, used as an alternative to
+/// shelling out to `jj`. The repository is opened once and reused across
+/// commits (wrapped for use from blocking tasks, since `git2::Repository`
+/// is not `Send` across an `.await`), so there are no process spawns at all.
+pub(crate) struct Git2Backend {
+    repo: Repository,
+}
+
+impl Git2Backend {
+    /// Opens the repository at `repo_path` once for reuse across commits.
+    pub(crate) fn open(repo_path: &Path) -> Result<Self> {
+        let repo = Repository::open(repo_path)
+            .context(format!("Failed to open git repository at {:?}", repo_path))?;
+        Ok(Self { repo })
+    }
+
+    /// Walks the revision range from HEAD back to the root commit(s),
+    /// mirroring the `root()..@` range used by the `jj` backend, and
+    /// returns `(commit_id, author_timestamp)` pairs oldest-first.
+    pub(crate) fn commit_history(&self) -> Result<Vec<(String, String)>> {
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.push_head()?;
+        revwalk.set_sorting(Sort::TIME | Sort::REVERSE)?;
+
+        let mut commits = Vec::new();
+        for oid in revwalk {
+            let oid = oid.context("Failed to read commit oid during revwalk")?;
+            let commit = self.repo.find_commit(oid)?;
+            let timestamp = commit.author().when().seconds().to_string();
+            commits.push((oid.to_string(), timestamp));
+        }
+        Ok(commits)
+    }
+
+    /// Extracts `file_to_extract` as it existed at `commit_id` by resolving
+    /// the tree entry for that path and reading the blob directly, no
+    /// subprocess involved. Returns `None` if the path doesn't exist in the
+    /// commit's tree (e.g. the file was added later).
+    pub(crate) fn extract_file(
+        &self,
+        commit_id: &str,
+        file_to_extract: &str,
+    ) -> Result<Option<Arc<Vec<u8>>>> {
+        let oid = Oid::from_str(commit_id)
+            .context(format!("'{}' is not a valid git commit id", commit_id))?;
+
+        let commit = self.repo.find_commit(oid)?;
+        let tree = commit.tree()?;
+        let entry = match tree.get_path(Path::new(file_to_extract)) {
+            Ok(entry) => entry,
+            Err(_) => {
+                warn!(
+                    "'{}' not present in tree for commit {}, skipping",
+                    file_to_extract, commit_id
+                );
+                return Ok(None);
+            }
+        };
+        let blob = entry
+            .to_object(&self.repo)
+            .context(format!("Failed to resolve tree entry for '{}'", file_to_extract))?
+            .into_blob()
+            .map_err(|_| {
+                anyhow::anyhow!("'{}' is not a blob at commit {}", file_to_extract, commit_id)
+            })?;
+
+        Ok(Some(Arc::new(blob.content().to_vec())))
+    }
+}
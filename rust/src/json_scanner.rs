@@ -1,136 +1,106 @@
-use std::collections::HashMap;
-use std::env;
-use std::fs::File;
-use std::io::BufReader;
-use std::path::Path;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use clap::{Parser, ValueEnum};
+use esa_act_history::schema::{self, Schema};
 use rayon::prelude::*;
-use serde_json::Value;
 use walkdir::WalkDir;
 
-// Type alias for our schema representation for clarity.
-// Outer HashMap: Key is the JSON field name (e.g., "user_id").
-// Inner HashMap: Key is the data type found (e.g., "String"), value is its count.
-type TypeCounts = HashMap<String, usize>;
-type Schema = HashMap<String, TypeCounts>;
+/// Format to serialize the aggregated schema report as.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum OutputFormat {
+    Json,
+    Toml,
+    Yaml,
+    Text,
+}
+
+/// Scan a directory for `.json` files and report the inferred schema of their contents.
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    /// Directory to recursively scan for `.json` files.
+    target_dir: PathBuf,
+
+    /// Write the schema report to this file instead of printing it to stdout.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Format to write `--output` as. Inferred from its file extension when omitted
+    /// (falling back to `text` for an unrecognized or missing extension).
+    #[arg(long, value_enum)]
+    output_format: Option<OutputFormat>,
+}
 
 fn main() -> anyhow::Result<()> {
-    // 1. Get the target directory from command-line arguments.
-    let target_dir = env::args().nth(1).ok_or_else(|| {
-        anyhow::anyhow!("Please provide a directory path as an argument.")
-    })?;
-    
-    let root_path = Path::new(&target_dir);
-    if !root_path.is_dir() {
+    let args = Args::parse();
+
+    if !args.target_dir.is_dir() {
         anyhow::bail!("Provided path is not a directory.");
     }
 
-    // 2. Use `walkdir` to find all files ending with .json.
-    let json_files: Vec<_> = WalkDir::new(root_path)
+    // Use `walkdir` to find all files ending with .json.
+    let json_files: Vec<_> = WalkDir::new(&args.target_dir)
         .into_iter()
         .filter_map(Result::ok)
-        .filter(|e| e.path().extension().map_or(false, |ext| ext == "json"))
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "json"))
         .map(|e| e.into_path())
         .collect();
 
     println!("Found {} JSON files. Starting parallel analysis... 🚀", json_files.len());
 
-    // 3. Process all files in parallel using Rayon.
+    // Process all files in parallel using Rayon, then reduce into one final schema.
     let final_schema = json_files
         .par_iter()
         .map(|path| {
-            // Analyze each file. If a file fails to parse, print a warning and return an empty schema.
-            analyze_file(path).unwrap_or_else(|err| {
+            schema::analyze_file(path).unwrap_or_else(|err| {
                 eprintln!("⚠️  Warning: Failed to process file {:?}: {}", path, err);
-                Schema::new()
+                Schema::default()
             })
         })
-        // Reduce the schemas from all threads into one final schema.
-        .reduce(Schema::new, merge_schemas);
-
-    // 4. Print the aggregated results in a clean, sorted format.
-    print_results(&final_schema);
-
-    Ok(())
-}
-
-/// Parses and analyzes a single JSON file.
-fn analyze_file(path: &Path) -> anyhow::Result<Schema> {
-    let file = File::open(path)?;
-    // Use a BufReader for efficiency, especially with larger files.
-    let reader = BufReader::new(file);
-
-    // Parse the file's JSON content into a vector of generic `Value`s.
-    let data: Vec<Value> = serde_json::from_reader(reader)?;
-
-    let mut schema = Schema::new();
-
-    // Iterate over each object in the top-level array.
-    for item in data {
-        if let Value::Object(map) = item {
-            // For each key-value pair in the object, record its type.
-            for (key, value) in map {
-                let type_name = get_value_type(&value).to_string();
-                schema
-                    .entry(key)
-                    .or_default()
-                    .entry(type_name)
-                    .and_modify(|count| *count += 1)
-                    .or_insert(1);
-            }
+        .reduce(Schema::default, schema::merge_schemas);
+
+    match &args.output {
+        Some(output_path) => {
+            let format = args
+                .output_format
+                .unwrap_or_else(|| infer_format(output_path));
+            write_report(&final_schema, output_path, format)?;
         }
+        None => schema::print_results(&final_schema),
     }
 
-    Ok(schema)
-}
-
-/// Merges two schema maps together. This is the 'reduce' step.
-fn merge_schemas(mut acc: Schema, other: Schema) -> Schema {
-    for (key, other_type_counts) in other {
-        let acc_type_counts = acc.entry(key).or_default();
-        for (type_name, count) in other_type_counts {
-            acc_type_counts
-                .entry(type_name)
-                .and_modify(|c| *c += count)
-                .or_insert(count);
-        }
-    }
-    acc
+    Ok(())
 }
 
-/// Returns a string slice representing the JSON value type.
-fn get_value_type(value: &Value) -> &'static str {
-    match value {
-        Value::Null => "Null",
-        Value::Bool(_) => "Boolean",
-        Value::Number(_) => "Number",
-        Value::String(_) => "String",
-        Value::Array(_) => "Array",
-        Value::Object(_) => "Object",
+/// Infers an output format from a file's extension, defaulting to `text`.
+fn infer_format(path: &Path) -> OutputFormat {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => OutputFormat::Json,
+        Some("toml") => OutputFormat::Toml,
+        Some("yaml") | Some("yml") => OutputFormat::Yaml,
+        _ => OutputFormat::Text,
     }
 }
 
-/// Prints the final analysis results to the console.
-fn print_results(schema: &Schema) {
-    println!("\n--- JSON Structure Analysis Results ---");
-
-    // Sort keys alphabetically for consistent, readable output.
-    let mut sorted_keys: Vec<_> = schema.keys().collect();
-    sorted_keys.sort();
-
-    for key in sorted_keys {
-        if let Some(type_counts) = schema.get(key) {
-            let total_occurrences: usize = type_counts.values().sum();
-            println!("\n## Key: '{}'", key);
-            println!("   - **Total Occurrences**: {}", total_occurrences);
-            println!("   - **Type Distribution**:");
-
-            let mut sorted_types: Vec<_> = type_counts.iter().collect();
-            sorted_types.sort_by_key(|k| k.0); // Sort by type name
+/// Serializes the schema report in `format` and writes it to `path`.
+fn write_report(schema: &Schema, path: &Path, format: OutputFormat) -> anyhow::Result<()> {
+    let report = schema.to_report();
 
-            for (type_name, count) in sorted_types {
-                let percentage = (*count as f64 / total_occurrences as f64) * 100.0;
-                println!("     - {:<10}: {:>10} ({:.2}%)", type_name, count, percentage);
-            }
+    let serialized = match format {
+        OutputFormat::Json => serde_json::to_string_pretty(&report)
+            .context("Failed to serialize schema report as JSON")?,
+        OutputFormat::Toml => {
+            toml::to_string_pretty(&report).context("Failed to serialize schema report as TOML")?
         }
-    }
-}
\ No newline at end of file
+        OutputFormat::Yaml => {
+            serde_yaml::to_string(&report).context("Failed to serialize schema report as YAML")?
+        }
+        OutputFormat::Text => schema::render_text_report(&report),
+    };
+
+    fs::write(path, serialized).context(format!("Failed to write output to {:?}", path))?;
+    println!("Wrote schema report to {:?}", path);
+    Ok(())
+}
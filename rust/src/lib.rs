@@ -0,0 +1,5 @@
+//! Shared library code used by both of this crate's binaries: the commit
+//! history extractor (`main.rs`) and the standalone schema scanner
+//! (`json_scanner.rs`).
+
+pub mod schema;
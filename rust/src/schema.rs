@@ -0,0 +1,429 @@
+//! JSON schema inference: given one or more JSON documents, record which
+//! types each field takes on and how often, recursing into nested objects
+//! and arrays.
+//!
+//! Nested fields are tracked under dotted paths (`geometry.coordinates`),
+//! and array elements are tracked one level deeper under a `[]` suffix
+//! (`features[].id`), alongside cardinality stats for the array itself.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::Context;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+// Type alias for a type-name -> count distribution, e.g. {"String": 12, "Null": 3}.
+// A BTreeMap keeps this sorted by type name so serialized reports are stable to diff.
+pub type TypeCounts = BTreeMap<String, usize>;
+
+/// Cardinality stats for an array observed at some field path.
+#[derive(Debug, Clone, Default)]
+pub struct ArrayStats {
+    pub min_len: usize,
+    pub max_len: usize,
+    sum_len: usize,
+    pub sample_count: usize,
+}
+
+impl ArrayStats {
+    pub fn avg_len(&self) -> f64 {
+        if self.sample_count == 0 {
+            0.0
+        } else {
+            self.sum_len as f64 / self.sample_count as f64
+        }
+    }
+
+    fn record(&mut self, len: usize) {
+        if self.sample_count == 0 {
+            self.min_len = len;
+            self.max_len = len;
+        } else {
+            self.min_len = self.min_len.min(len);
+            self.max_len = self.max_len.max(len);
+        }
+        self.sum_len += len;
+        self.sample_count += 1;
+    }
+
+    fn merge(&mut self, other: &ArrayStats) {
+        if other.sample_count == 0 {
+            return;
+        }
+        if self.sample_count == 0 {
+            *self = other.clone();
+            return;
+        }
+        self.min_len = self.min_len.min(other.min_len);
+        self.max_len = self.max_len.max(other.max_len);
+        self.sum_len += other.sum_len;
+        self.sample_count += other.sample_count;
+    }
+}
+
+/// Everything observed about one field path across all analyzed documents.
+#[derive(Debug, Clone, Default)]
+pub struct FieldStats {
+    pub type_counts: TypeCounts,
+    /// Number of times this path was present (of any type).
+    pub occurrences: usize,
+    /// Set when at least one observed value at this path was an array.
+    pub array_stats: Option<ArrayStats>,
+}
+
+/// A schema inferred over one or more JSON documents.
+#[derive(Debug, Clone, Default)]
+pub struct Schema {
+    /// Keyed by dotted field path, e.g. "geometry.coordinates" or "features[].id".
+    pub fields: BTreeMap<String, FieldStats>,
+    /// Total number of top-level documents (objects) analyzed.
+    pub documents_seen: usize,
+}
+
+impl Schema {
+    /// Returns whether `path` was omitted by at least one sibling instance
+    /// of its parent container (top-level documents for a bare key, or the
+    /// parent object/array for a nested path).
+    pub fn is_optional(&self, path: &str) -> bool {
+        let denominator = match parent_path(path) {
+            Some(parent) => self.fields.get(&parent).map_or(0, |stats| stats.occurrences),
+            None => self.documents_seen,
+        };
+        self.fields
+            .get(path)
+            .is_none_or(|stats| stats.occurrences < denominator)
+    }
+
+    /// Builds a serializable, percentage-annotated report from this schema,
+    /// suitable for writing out as JSON/TOML/YAML and diffing between runs.
+    pub fn to_report(&self) -> SchemaReport {
+        let fields = self
+            .fields
+            .iter()
+            .map(|(path, stats)| (path.clone(), FieldReport::from_stats(stats, self.is_optional(path))))
+            .collect();
+
+        SchemaReport {
+            documents_seen: self.documents_seen,
+            fields,
+        }
+    }
+}
+
+/// A schema snapshot in report form: per-type counts are expressed with
+/// their percentage of a field's total occurrences, and fields are kept in
+/// a `BTreeMap` so the serialized output is stably sorted.
+#[derive(Debug, Clone, Serialize)]
+pub struct SchemaReport {
+    pub documents_seen: usize,
+    pub fields: BTreeMap<String, FieldReport>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldReport {
+    pub occurrences: usize,
+    pub optional: bool,
+    pub types: BTreeMap<String, TypeReport>,
+    pub array_stats: Option<ArrayStatsReport>,
+}
+
+impl FieldReport {
+    fn from_stats(stats: &FieldStats, optional: bool) -> Self {
+        let total_occurrences: usize = stats.type_counts.values().sum();
+        let types = stats
+            .type_counts
+            .iter()
+            .map(|(type_name, count)| {
+                let percentage = if total_occurrences == 0 {
+                    0.0
+                } else {
+                    *count as f64 / total_occurrences as f64 * 100.0
+                };
+                (type_name.clone(), TypeReport { count: *count, percentage })
+            })
+            .collect();
+
+        FieldReport {
+            occurrences: stats.occurrences,
+            optional,
+            types,
+            array_stats: stats.array_stats.as_ref().map(ArrayStatsReport::from),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TypeReport {
+    pub count: usize,
+    pub percentage: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ArrayStatsReport {
+    pub min_len: usize,
+    pub max_len: usize,
+    pub avg_len: f64,
+    pub sample_count: usize,
+}
+
+impl From<&ArrayStats> for ArrayStatsReport {
+    fn from(stats: &ArrayStats) -> Self {
+        ArrayStatsReport {
+            min_len: stats.min_len,
+            max_len: stats.max_len,
+            avg_len: stats.avg_len(),
+            sample_count: stats.sample_count,
+        }
+    }
+}
+
+/// The path of the container `path` was found in, e.g. "a.b[]" for "a.b[].c".
+fn parent_path(path: &str) -> Option<String> {
+    path.rsplit_once('.').map(|(parent, _)| parent.to_string())
+}
+
+/// Parses and analyzes a single JSON file on disk: either a top-level array
+/// of objects, or newline-delimited JSON (one document per line).
+pub fn analyze_file(path: &Path) -> anyhow::Result<Schema> {
+    let content = fs::read(path).context(format!("Failed to read {:?}", path))?;
+    analyze_bytes(&content)
+}
+
+/// Parses and analyzes JSON content already held in memory, e.g. content
+/// extracted from a historical commit rather than read from disk.
+pub fn analyze_bytes(content: &[u8]) -> anyhow::Result<Schema> {
+    let documents = parse_documents(content)?;
+    Ok(analyze_documents(documents))
+}
+
+/// Parses `content` as a top-level JSON array of objects. If that fails
+/// (e.g. the content isn't a JSON array at all), falls back to treating it
+/// as NDJSON: one JSON value per non-empty line.
+fn parse_documents(content: &[u8]) -> anyhow::Result<Vec<Value>> {
+    match serde_json::from_slice::<Vec<Value>>(content) {
+        Ok(documents) => Ok(documents),
+        Err(_) => parse_ndjson(content),
+    }
+}
+
+fn parse_ndjson(content: &[u8]) -> anyhow::Result<Vec<Value>> {
+    let text = std::str::from_utf8(content).context("Content is neither a JSON array nor valid UTF-8 NDJSON")?;
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| serde_json::from_str(line).context("Failed to parse an NDJSON line"))
+        .collect()
+}
+
+/// Builds a schema from a set of top-level JSON documents, recursing into
+/// nested objects and arrays.
+fn analyze_documents(documents: Vec<Value>) -> Schema {
+    let mut schema = Schema::default();
+
+    for document in documents {
+        if let Value::Object(map) = document {
+            schema.documents_seen += 1;
+            record_object(&mut schema, "", &map);
+        }
+    }
+
+    schema
+}
+
+fn record_object(schema: &mut Schema, prefix: &str, map: &Map<String, Value>) {
+    for (key, value) in map {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        record_value(schema, &path, value);
+    }
+}
+
+fn record_value(schema: &mut Schema, path: &str, value: &Value) {
+    let type_name = get_value_type(value).to_string();
+
+    {
+        let stats = schema.fields.entry(path.to_string()).or_default();
+        stats.occurrences += 1;
+        *stats.type_counts.entry(type_name).or_insert(0) += 1;
+        if let Value::Array(items) = value {
+            stats
+                .array_stats
+                .get_or_insert_with(ArrayStats::default)
+                .record(items.len());
+        }
+    }
+
+    match value {
+        Value::Object(map) => record_object(schema, path, map),
+        Value::Array(items) => {
+            let element_path = format!("{}[]", path);
+            for item in items {
+                record_value(schema, &element_path, item);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Merges two schemas together. This is the 'reduce' step.
+pub fn merge_schemas(mut acc: Schema, other: Schema) -> Schema {
+    acc.documents_seen += other.documents_seen;
+
+    for (path, other_stats) in other.fields {
+        let acc_stats = acc.fields.entry(path).or_default();
+        acc_stats.occurrences += other_stats.occurrences;
+        for (type_name, count) in other_stats.type_counts {
+            *acc_stats.type_counts.entry(type_name).or_insert(0) += count;
+        }
+        if let Some(other_array) = &other_stats.array_stats {
+            acc_stats
+                .array_stats
+                .get_or_insert_with(ArrayStats::default)
+                .merge(other_array);
+        }
+    }
+
+    acc
+}
+
+/// Returns a string slice representing the JSON value type.
+pub fn get_value_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "Null",
+        Value::Bool(_) => "Boolean",
+        Value::Number(_) => "Number",
+        Value::String(_) => "String",
+        Value::Array(_) => "Array",
+        Value::Object(_) => "Object",
+    }
+}
+
+/// Returns the most frequently observed type for a field, breaking ties by
+/// type name (the alphabetically first name wins) so the result is
+/// deterministic.
+pub fn dominant_type(type_counts: &TypeCounts) -> Option<&str> {
+    type_counts
+        .iter()
+        .max_by(|(a_name, a_count), (b_name, b_count)| {
+            a_count.cmp(b_count).then_with(|| b_name.cmp(a_name))
+        })
+        .map(|(name, _)| name.as_str())
+}
+
+/// Prints the final analysis results to the console.
+pub fn print_results(schema: &Schema) {
+    print!("{}", render_text_report(&schema.to_report()));
+}
+
+/// Renders a schema report as the same human-readable text `print_results`
+/// prints to stdout, for reuse when writing a text-format `--output` file.
+pub fn render_text_report(report: &SchemaReport) -> String {
+    use std::fmt::Write;
+
+    let mut out = String::new();
+    writeln!(out, "\n--- JSON Structure Analysis Results ---").unwrap();
+    writeln!(out, "Documents analyzed: {}", report.documents_seen).unwrap();
+
+    // `report.fields` is a BTreeMap, so this is already in sorted path order.
+    for (path, field) in &report.fields {
+        let optional_marker = if field.optional { " (optional)" } else { "" };
+
+        writeln!(out, "\n## Field: '{}'{}", path, optional_marker).unwrap();
+        writeln!(out, "   - **Occurrences**: {}", field.occurrences).unwrap();
+        writeln!(out, "   - **Type Distribution**:").unwrap();
+
+        for (type_name, type_report) in &field.types {
+            writeln!(
+                out,
+                "     - {:<10}: {:>10} ({:.2}%)",
+                type_name, type_report.count, type_report.percentage
+            )
+            .unwrap();
+        }
+
+        if let Some(array_stats) = &field.array_stats {
+            writeln!(
+                out,
+                "   - **Array length**: min={} max={} avg={:.2}",
+                array_stats.min_len, array_stats.max_len, array_stats.avg_len
+            )
+            .unwrap();
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dominant_type_picks_the_highest_count() {
+        let mut counts = TypeCounts::new();
+        counts.insert("String".to_string(), 1);
+        counts.insert("Number".to_string(), 3);
+        assert_eq!(dominant_type(&counts), Some("Number"));
+    }
+
+    #[test]
+    fn dominant_type_breaks_ties_by_type_name() {
+        let mut counts = TypeCounts::new();
+        counts.insert("Number".to_string(), 2);
+        counts.insert("String".to_string(), 2);
+        assert_eq!(dominant_type(&counts), Some("Number"));
+    }
+
+    #[test]
+    fn dominant_type_of_empty_counts_is_none() {
+        assert_eq!(dominant_type(&TypeCounts::new()), None);
+    }
+
+    #[test]
+    fn is_optional_is_false_when_every_document_has_the_field() {
+        let schema = analyze_bytes(br#"[{"a": 1}, {"a": 2}]"#).unwrap();
+        assert!(!schema.is_optional("a"));
+    }
+
+    #[test]
+    fn is_optional_is_true_when_some_document_omits_the_field() {
+        let schema = analyze_bytes(br#"[{"a": 1}, {}]"#).unwrap();
+        assert!(schema.is_optional("a"));
+    }
+
+    #[test]
+    fn is_optional_uses_the_parent_containers_occurrences_for_nested_paths() {
+        // "b" is present in every "a" object that itself appears, so it's
+        // not optional relative to its parent even though "a" itself is
+        // missing from the second document.
+        let schema = analyze_bytes(br#"[{"a": {"b": 1}}, {}]"#).unwrap();
+        assert!(schema.is_optional("a"));
+        assert!(!schema.is_optional("a.b"));
+    }
+
+    #[test]
+    fn record_value_tracks_array_length_stats_recursively() {
+        let schema = analyze_bytes(br#"[{"items": [1, 2, 3]}, {"items": [1]}]"#).unwrap();
+        let stats = schema.fields.get("items").unwrap();
+        let array_stats = stats.array_stats.as_ref().unwrap();
+        assert_eq!(array_stats.min_len, 1);
+        assert_eq!(array_stats.max_len, 3);
+        assert_eq!(array_stats.avg_len(), 2.0);
+
+        // Each array element is also recorded under the "[]" path.
+        let element_stats = schema.fields.get("items[]").unwrap();
+        assert_eq!(element_stats.occurrences, 4);
+    }
+
+    #[test]
+    fn analyze_bytes_falls_back_to_ndjson_when_not_a_json_array() {
+        let schema = analyze_bytes(b"{\"a\": 1}\n{\"a\": 2}\n").unwrap();
+        assert_eq!(schema.documents_seen, 2);
+        assert_eq!(schema.fields.get("a").unwrap().occurrences, 2);
+    }
+}
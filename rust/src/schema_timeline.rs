@@ -0,0 +1,233 @@
+//! Ties commit history extraction together with schema inference: walks a
+//! file's history oldest-to-newest and reports the commits where its
+//! inferred schema changed shape.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use tracing::{info, warn};
+
+use esa_act_history::schema::{self, Schema};
+
+use crate::find_change::sort_oldest_first;
+use crate::{fetch_file_via_jj, get_commit_history, git2_backend::Git2Backend, Backend};
+use crate::{DEFAULT_FILE_TO_EXTRACT, JJ_COMMAND_NAME};
+
+/// Report how the inferred schema of a file changed, commit by commit.
+#[derive(Parser, Debug)]
+pub(crate) struct SchemaTimelineArgs {
+    /// The name of the file to track, relative to the repository root.
+    #[arg(short, long, default_value = DEFAULT_FILE_TO_EXTRACT)]
+    file_to_extract: String,
+
+    /// Override the path to the 'jj' executable if it's not in your system's PATH.
+    #[arg(long)]
+    jj_path: Option<PathBuf>,
+
+    /// Which extraction backend to use.
+    #[arg(long, value_enum, default_value_t = Backend::Jj)]
+    backend: Backend,
+
+    /// Path to the repository root. Only used by the `git2` backend, which
+    /// (unlike the `jj` backend) doesn't assume the current directory.
+    #[arg(long, default_value = ".")]
+    repo_path: PathBuf,
+}
+
+/// One observed change between two consecutive schema snapshots.
+#[derive(Debug, PartialEq)]
+enum SchemaChange {
+    KeyAdded { key: String },
+    KeyRemoved { key: String },
+    TypeChanged { key: String, from: String, to: String },
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaChange::KeyAdded { key } => write!(f, "+ '{}' appeared", key),
+            SchemaChange::KeyRemoved { key } => write!(f, "- '{}' disappeared", key),
+            SchemaChange::TypeChanged { key, from, to } => {
+                write!(f, "~ '{}' changed type: {} -> {}", key, from, to)
+            }
+        }
+    }
+}
+
+/// Runs the `schema-timeline` subcommand.
+pub(crate) async fn run(args: &SchemaTimelineArgs) -> Result<()> {
+    match args.backend {
+        Backend::Jj => run_jj(args).await,
+        Backend::Git2 => run_git2(args).await,
+    }
+}
+
+async fn run_jj(args: &SchemaTimelineArgs) -> Result<()> {
+    let jj_executable = args.jj_path.as_deref().unwrap_or(Path::new(JJ_COMMAND_NAME));
+
+    let mut commits = get_commit_history(jj_executable).await?;
+    sort_oldest_first(&mut commits);
+    info!("Walking {} commits to build the schema timeline...", commits.len());
+
+    let mut previous_schema: Option<Schema> = None;
+    let mut changes_found = 0;
+
+    for (commit_id, timestamp) in commits {
+        let content = match fetch_file_via_jj(jj_executable, &commit_id, &args.file_to_extract).await {
+            Ok(content) => content,
+            Err(e) => {
+                warn!("Skipping commit {}: {:?}", commit_id, e);
+                continue;
+            }
+        };
+
+        changes_found += report_changes(&mut previous_schema, &content, &commit_id, &timestamp, &args.file_to_extract);
+    }
+
+    info!("Schema timeline complete. {} change(s) found.", changes_found);
+    Ok(())
+}
+
+async fn run_git2(args: &SchemaTimelineArgs) -> Result<()> {
+    let repo_path = args.repo_path.clone();
+    let file_to_extract = args.file_to_extract.clone();
+
+    tokio::task::spawn_blocking(move || -> Result<()> {
+        let backend = Git2Backend::open(&repo_path)?;
+        let commits = backend.commit_history()?;
+        info!("Walking {} commits to build the schema timeline...", commits.len());
+
+        let mut previous_schema: Option<Schema> = None;
+        let mut changes_found = 0;
+
+        for (commit_id, timestamp) in commits {
+            let content = match backend.extract_file(&commit_id, &file_to_extract) {
+                Ok(Some(content)) => content,
+                Ok(None) => continue,
+                Err(e) => {
+                    warn!("Skipping commit {}: {:?}", commit_id, e);
+                    continue;
+                }
+            };
+
+            changes_found += report_changes(&mut previous_schema, &content, &commit_id, &timestamp, &file_to_extract);
+        }
+
+        info!("Schema timeline complete. {} change(s) found.", changes_found);
+        Ok(())
+    })
+    .await
+    .context("git2 backend task panicked")?
+}
+
+/// Infers the schema for one version of the file, diffs it against the
+/// previous version (if any), and prints the resulting changes. Returns the
+/// number of changes printed.
+fn report_changes(
+    previous_schema: &mut Option<Schema>,
+    content: &[u8],
+    commit_id: &str,
+    timestamp: &str,
+    file_to_extract: &str,
+) -> usize {
+    let current_schema = match schema::analyze_bytes(content) {
+        Ok(schema) => schema,
+        Err(e) => {
+            warn!(
+                "Skipping commit {} ({}): failed to parse '{}' as JSON: {}",
+                commit_id, timestamp, file_to_extract, e
+            );
+            return 0;
+        }
+    };
+
+    let mut printed = 0;
+    if let Some(previous) = previous_schema.as_ref() {
+        for change in diff_schemas(previous, &current_schema) {
+            println!("[{} @ {}] {}", timestamp, commit_id, change);
+            printed += 1;
+        }
+    }
+
+    *previous_schema = Some(current_schema);
+    printed
+}
+
+/// Compares two consecutive schema snapshots and reports added fields, removed
+/// fields, and fields whose dominant type changed, in stable path order.
+fn diff_schemas(previous: &Schema, current: &Schema) -> Vec<SchemaChange> {
+    let mut changes = Vec::new();
+
+    let mut paths: Vec<&String> = previous.fields.keys().chain(current.fields.keys()).collect();
+    paths.sort();
+    paths.dedup();
+
+    for path in paths {
+        match (previous.fields.get(path), current.fields.get(path)) {
+            (None, Some(_)) => changes.push(SchemaChange::KeyAdded { key: path.clone() }),
+            (Some(_), None) => changes.push(SchemaChange::KeyRemoved { key: path.clone() }),
+            (Some(prev_stats), Some(curr_stats)) => {
+                let prev_type = schema::dominant_type(&prev_stats.type_counts);
+                let curr_type = schema::dominant_type(&curr_stats.type_counts);
+                if let (Some(from), Some(to)) = (prev_type, curr_type) {
+                    if from != to {
+                        changes.push(SchemaChange::TypeChanged {
+                            key: path.clone(),
+                            from: from.to_string(),
+                            to: to.to_string(),
+                        });
+                    }
+                }
+            }
+            (None, None) => {}
+        }
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema_of(documents: &[u8]) -> Schema {
+        schema::analyze_bytes(documents).unwrap()
+    }
+
+    #[test]
+    fn diff_schemas_reports_an_added_field() {
+        let previous = schema_of(br#"[{"a": 1}]"#);
+        let current = schema_of(br#"[{"a": 1, "b": 2}]"#);
+        assert_eq!(diff_schemas(&previous, &current), vec![SchemaChange::KeyAdded { key: "b".to_string() }]);
+    }
+
+    #[test]
+    fn diff_schemas_reports_a_removed_field() {
+        let previous = schema_of(br#"[{"a": 1, "b": 2}]"#);
+        let current = schema_of(br#"[{"a": 1}]"#);
+        assert_eq!(diff_schemas(&previous, &current), vec![SchemaChange::KeyRemoved { key: "b".to_string() }]);
+    }
+
+    #[test]
+    fn diff_schemas_reports_a_type_change() {
+        let previous = schema_of(br#"[{"a": 1}]"#);
+        let current = schema_of(br#"[{"a": "one"}]"#);
+        assert_eq!(
+            diff_schemas(&previous, &current),
+            vec![SchemaChange::TypeChanged {
+                key: "a".to_string(),
+                from: "Number".to_string(),
+                to: "String".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn diff_schemas_is_empty_when_nothing_changed() {
+        let previous = schema_of(br#"[{"a": 1}]"#);
+        let current = schema_of(br#"[{"a": 2}]"#);
+        assert!(diff_schemas(&previous, &current).is_empty());
+    }
+}